@@ -13,6 +13,13 @@
 //! * provide a way to determine the start and end of a sequence of messages
 //! * use a shared secret, such as a passphrase, which can be used to derive a
 //!   secret key using `crypto_pwhash_*`
+//! * encrypt or decrypt a large file or socket without buffering the whole
+//!   thing in memory, using [`DryocStreamWriter`] / [`DryocStreamReader`]
+//!
+//! [`DryocStream`] is generic over its AEAD backend via the
+//! [`SecretStreamCipher`] trait, defaulting to the
+//! [`XChaCha20Poly1305Cipher`] implementation used by the rest of this
+//! crate.
 //!
 //! # Rustaceous API example
 //!
@@ -26,7 +33,8 @@
 //! let key = Key::gen();
 //!
 //! // Initialize the push side, type annotations required on return type
-//! let (mut push_stream, header): (_, Header) = DryocStream::init_push(&key);
+//! let (mut push_stream, header): (_, Header) =
+//!     DryocStream::<Push>::init_push(&key).expect("push init failed");
 
 //! // Encrypt a series of messages
 //! let c1 = push_stream
@@ -40,7 +48,8 @@
 //! .expect("Encrypt failed");
 //!
 //! // Initialize the pull side using header generated by the push side
-//! let mut pull_stream = DryocStream::init_pull(&key, &header);
+//! let mut pull_stream =
+//!     DryocStream::<Pull>::init_pull(&key, &header).expect("pull init failed");
 //!
 //! // Decrypt the encrypted messages, type annotations required
 //! let (m1, tag1) = pull_stream.pull_to_vec(&c1, None).expect("Decrypt
@@ -66,15 +75,22 @@
 //! * See [protected] for an example using the protected memory features with
 //!   [`DryocStream`]
 
+use std::convert::{TryFrom, TryInto};
+use std::io::{self, Read, Write};
+
 use bitflags::bitflags;
 use zeroize::Zeroize;
 
+use crate::classic::crypto_pwhash::crypto_pwhash;
 use crate::classic::crypto_secretstream_xchacha20poly1305::{
     crypto_secretstream_xchacha20poly1305_init_pull,
     crypto_secretstream_xchacha20poly1305_init_push, crypto_secretstream_xchacha20poly1305_pull,
     crypto_secretstream_xchacha20poly1305_push, crypto_secretstream_xchacha20poly1305_rekey, State,
 };
 use crate::constants::{
+    CRYPTO_PWHASH_ALG_DEFAULT, CRYPTO_PWHASH_MEMLIMIT_INTERACTIVE,
+    CRYPTO_PWHASH_OPSLIMIT_INTERACTIVE, CRYPTO_PWHASH_SALTBYTES,
+    CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_ABYTES,
     CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_HEADERBYTES,
     CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_KEYBYTES,
     CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_TAG_MESSAGE,
@@ -100,6 +116,9 @@ pub type Key = StackByteArray<CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_KEYBYTES>;
 pub type Nonce = StackByteArray<CRYPTO_STREAM_CHACHA20_IETF_NONCEBYTES>;
 /// Stack-allocated header data for authenticated secret streams.
 pub type Header = StackByteArray<CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_HEADERBYTES>;
+/// Stack-allocated salt used to derive a [`Key`] from a passphrase, via
+/// [`DryocStream::seal_with_passphrase`].
+pub type PwHashSalt = StackByteArray<CRYPTO_PWHASH_SALTBYTES>;
 
 #[cfg(any(feature = "nightly", all(doc, not(doctest))))]
 #[cfg_attr(all(feature = "nightly", doc), doc(cfg(feature = "nightly")))]
@@ -126,7 +145,9 @@ pub mod protected {
     //! let key = Key::gen_readonly_locked().expect("key failed");
     //!
     //! // Initialize the push stream, place the header into locked memory
-    //! let (mut push_stream, header): (_, Locked<Header>) = DryocStream::init_push(&key);
+    //! use dryoc::dryocstream::{Pull, Push};
+    //! let (mut push_stream, header): (_, Locked<Header>) =
+    //!     DryocStream::<Push>::init_push(&key).expect("push init failed");
     //!
     //! // Encrypt the set of messages, placing everything into locked memory.
     //! let c1: LockedBytes = push_stream
@@ -140,7 +161,8 @@ pub mod protected {
     //!     .expect("Encrypt failed");
     //!
     //! // Initialized the pull stream
-    //! let mut pull_stream = DryocStream::init_pull(&key, &header);
+    //! let mut pull_stream =
+    //!     DryocStream::<Pull>::init_pull(&key, &header).expect("pull init failed");
     //!
     //! // Decrypt the set of messages, putting everything into locked memory
     //! let (m1, tag1): (LockedBytes, Tag) = pull_stream.pull(&c1, None).expect("Decrypt failed");
@@ -191,52 +213,259 @@ impl From<u8> for Tag {
     }
 }
 
-/// Secret-key authenticated encrypted streams
+/// Suggested number of `push`/`pull` calls between automatic rekeys, to
+/// pass to [`DryocStream::with_rekey_interval`] (which always takes an
+/// explicit interval; nothing applies this value automatically). This
+/// mirrors the interval used by the FSChaCha20Poly1305 scheme, bounding the amount of data
+/// protected by any single key to 2^24 messages.
+pub const DEFAULT_REKEY_INTERVAL: u64 = 1 << 24;
+
+/// Identifies which [`SecretStreamCipher`] backend produced a stream.
+///
+/// Currently only [`DryocStream::seal_with_passphrase`] (and
+/// [`DryocStream::open_with_passphrase`]) encode this as a byte
+/// immediately ahead of the [`Header`] in their blob format;
+/// [`DryocStreamWriter`]/[`DryocStreamReader`] and the `tokio` adapters
+/// don't embed it, so both ends of those streams must already agree out
+/// of band on which backend they're using.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CipherKind {
+    /// [`XChaCha20Poly1305Cipher`], the default and only built-in backend.
+    XChaCha20Poly1305 = 1,
+}
+
+impl TryFrom<u8> for CipherKind {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Error> {
+        match value {
+            1 => Ok(Self::XChaCha20Poly1305),
+            other => Err(Error::Unknown(format!(
+                "unrecognized secretstream cipher kind {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// A pluggable AEAD backend for [`DryocStream`].
+///
+/// [`XChaCha20Poly1305Cipher`] is the default implementor, used unless a
+/// different backend is given as `DryocStream`'s second type parameter.
+/// Implementing this trait for another AEAD lets it reuse all of the
+/// push/pull/[`Tag`]/rekeying logic in this module without duplicating
+/// it.
+pub trait SecretStreamCipher {
+    /// Per-cipher state carried across `push`/`pull` calls.
+    type State: Clone + PartialEq + Zeroize;
+
+    /// Identifies this backend in a stream's encoded [`CipherKind`] byte.
+    const KIND: CipherKind;
+    /// Secret key length, in bytes.
+    const KEYBYTES: usize;
+    /// Stream header length, in bytes.
+    const HEADERBYTES: usize;
+    /// Authentication tag overhead added to each block, in bytes.
+    const ABYTES: usize;
+
+    /// Initializes fresh state for the push side, filling in `header`.
+    fn init_push(key: &[u8], header: &mut [u8]) -> Self::State;
+    /// Initializes fresh state for the pull side, from `key` and `header`.
+    fn init_pull(key: &[u8], header: &[u8]) -> Self::State;
+    /// Encrypts `message` into `ciphertext` (already sized to
+    /// `message.len() + Self::ABYTES`), tagged with `tag`.
+    fn push(
+        state: &mut Self::State,
+        ciphertext: &mut [u8],
+        message: &[u8],
+        associated_data: Option<&[u8]>,
+        tag: u8,
+    ) -> Result<(), Error>;
+    /// Decrypts `ciphertext` into `message` (already sized to
+    /// `ciphertext.len() - Self::ABYTES`), returning the block's tag.
+    fn pull(
+        state: &mut Self::State,
+        message: &mut [u8],
+        ciphertext: &[u8],
+        associated_data: Option<&[u8]>,
+    ) -> Result<u8, Error>;
+    /// Derives a new key for the stream.
+    fn rekey(state: &mut Self::State);
+}
+
+/// The default [`SecretStreamCipher`] implementor: libsodium's
+/// XChaCha20-Poly1305-based secretstream construction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XChaCha20Poly1305Cipher;
+
+impl SecretStreamCipher for XChaCha20Poly1305Cipher {
+    type State = State;
+
+    const ABYTES: usize = CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_ABYTES;
+    const HEADERBYTES: usize = CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_HEADERBYTES;
+    const KEYBYTES: usize = CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_KEYBYTES;
+    const KIND: CipherKind = CipherKind::XChaCha20Poly1305;
+
+    fn init_push(key: &[u8], header: &mut [u8]) -> Self::State {
+        let mut state = State::new();
+        crypto_secretstream_xchacha20poly1305_init_push(
+            &mut state,
+            header.try_into().expect("header length"),
+            key.try_into().expect("key length"),
+        );
+        state
+    }
+
+    fn init_pull(key: &[u8], header: &[u8]) -> Self::State {
+        let mut state = State::new();
+        crypto_secretstream_xchacha20poly1305_init_pull(
+            &mut state,
+            header.try_into().expect("header length"),
+            key.try_into().expect("key length"),
+        );
+        state
+    }
+
+    fn push(
+        state: &mut Self::State,
+        ciphertext: &mut [u8],
+        message: &[u8],
+        associated_data: Option<&[u8]>,
+        tag: u8,
+    ) -> Result<(), Error> {
+        crypto_secretstream_xchacha20poly1305_push(
+            state,
+            ciphertext,
+            message,
+            associated_data,
+            tag,
+        )
+    }
+
+    fn pull(
+        state: &mut Self::State,
+        message: &mut [u8],
+        ciphertext: &[u8],
+        associated_data: Option<&[u8]>,
+    ) -> Result<u8, Error> {
+        let mut tag = 0u8;
+        crypto_secretstream_xchacha20poly1305_pull(state, message, &mut tag, ciphertext, associated_data)?;
+        Ok(tag)
+    }
+
+    fn rekey(state: &mut Self::State) {
+        crypto_secretstream_xchacha20poly1305_rekey(state)
+    }
+}
+
+/// Secret-key authenticated encrypted streams, generic over the
+/// [`SecretStreamCipher`] backend `C` (defaulting to
+/// [`XChaCha20Poly1305Cipher`]).
 #[derive(PartialEq, Clone, Zeroize)]
-pub struct DryocStream<Mode> {
+pub struct DryocStream<Mode, C: SecretStreamCipher = XChaCha20Poly1305Cipher> {
     #[zeroize(drop)]
-    state: State,
+    state: C::State,
     phantom: std::marker::PhantomData<Mode>,
+    rekey_interval: Option<u64>,
+    message_count: u64,
 }
 
-impl<M> DryocStream<M> {
+impl<M, C: SecretStreamCipher> DryocStream<M, C> {
     /// Manually rekeys the stream. Both the push and pull sides of the stream
     /// need to manually rekey if you use this function (i.e., it's not handled
     /// by the library).
     ///
-    /// Automatic rekeying will occur normally, and you generally should need to
-    /// manually rekey.
+    /// This is independent of the automatic rekeying performed when a
+    /// stream is created with [`DryocStream::with_rekey_interval`]: manual
+    /// rekeys don't reset the automatic-rekey message counter, and vice
+    /// versa.
     ///
     /// Refer to the [libsodium
     /// docs](https://libsodium.gitbook.io/doc/secret-key_cryptography/secretstream#rekeying)
     /// for details.
     pub fn rekey(&mut self) {
-        crypto_secretstream_xchacha20poly1305_rekey(&mut self.state)
+        C::rekey(&mut self.state)
+    }
+
+    /// Sets the automatic rekey interval, in number of `push`/`pull` calls,
+    /// to `rekey_interval`. Both sides of the stream must be configured
+    /// with the same interval, and the counter is bumped on every
+    /// processed frame regardless of its [`Tag`], so the two sides rekey
+    /// at identical message boundaries.
+    ///
+    /// Returns an [`Error`] if `rekey_interval` is zero, since the
+    /// interval is used as a modulus when checking whether to rekey.
+    pub fn set_rekey_interval(&mut self, rekey_interval: u64) -> Result<(), Error> {
+        if rekey_interval == 0 {
+            return Err(Error::Unknown(
+                "rekey_interval must be greater than zero".to_string(),
+            ));
+        }
+        self.rekey_interval = Some(rekey_interval);
+        Ok(())
+    }
+
+    /// Returns the [`CipherKind`] of the backend `C` used by this stream.
+    pub fn kind() -> CipherKind {
+        C::KIND
+    }
+
+    fn bump_message_count(&mut self) {
+        if let Some(rekey_interval) = self.rekey_interval {
+            self.message_count += 1;
+            if self.message_count % rekey_interval == 0 {
+                self.rekey();
+            }
+        }
     }
 }
 
-impl DryocStream<Push> {
+impl<C: SecretStreamCipher> DryocStream<Push, C> {
     /// Returns a new push stream, initialized from `key`.
-    pub fn init_push<
-        Key: ByteArray<CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_KEYBYTES>,
-        Header: NewByteArray<CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_HEADERBYTES>,
-    >(
+    ///
+    /// Returns an [`Error`] if `key`'s length doesn't match
+    /// `C::KEYBYTES`, since `Key`/`Header` are generic byte containers
+    /// here rather than arrays fixed to a particular backend's sizes (so
+    /// that a [`SecretStreamCipher`] with different key/header lengths
+    /// than [`XChaCha20Poly1305Cipher`] can still be used).
+    pub fn init_push<Key: Bytes, Header: NewBytes + ResizableBytes>(
         key: &Key,
-    ) -> (Self, Header) {
-        let mut state = State::new();
-        let mut header = Header::new_byte_array();
-        crypto_secretstream_xchacha20poly1305_init_push(
-            &mut state,
-            header.as_mut_array(),
-            key.as_array(),
-        );
-        (
+    ) -> Result<(Self, Header), Error> {
+        if key.as_slice().len() != C::KEYBYTES {
+            return Err(Error::Unknown(format!(
+                "key must be {} bytes for this cipher, got {}",
+                C::KEYBYTES,
+                key.as_slice().len()
+            )));
+        }
+        let mut header = Header::new_bytes();
+        header.resize(C::HEADERBYTES, 0);
+        let state = C::init_push(key.as_slice(), header.as_mut_slice());
+        Ok((
             Self {
                 state,
                 phantom: std::marker::PhantomData,
+                rekey_interval: None,
+                message_count: 0,
             },
             header,
-        )
+        ))
+    }
+
+    /// Returns a new push stream, initialized from `key`, which
+    /// automatically calls [`DryocStream::rekey`] every `rekey_interval`
+    /// calls to [`DryocStream::push`] (see [`DEFAULT_REKEY_INTERVAL`] for a
+    /// sensible default). The pull side must be created with
+    /// [`DryocStream::with_rekey_interval`] using the same
+    /// `rekey_interval` so both sides rekey at the same message boundary.
+    pub fn with_rekey_interval<Key: Bytes, Header: NewBytes + ResizableBytes>(
+        key: &Key,
+        rekey_interval: u64,
+    ) -> Result<(Self, Header), Error> {
+        let (mut stream, header) = Self::init_push(key)?;
+        stream.set_rekey_interval(rekey_interval)?;
+        Ok((stream, header))
     }
 
     /// Encrypts `message` for this stream with `associated_data` and `tag`,
@@ -247,19 +476,16 @@ impl DryocStream<Push> {
         associated_data: Option<&Input>,
         tag: Tag,
     ) -> Result<Output, Error> {
-        use crate::constants::CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_ABYTES;
         let mut ciphertext = Output::new_bytes();
-        ciphertext.resize(
-            message.as_slice().len() + CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_ABYTES,
-            0,
-        );
-        crypto_secretstream_xchacha20poly1305_push(
+        ciphertext.resize(message.as_slice().len() + C::ABYTES, 0);
+        C::push(
             &mut self.state,
             ciphertext.as_mut_slice(),
             message.as_slice(),
             associated_data.map(|aad| aad.as_slice()),
             tag.bits(),
         )?;
+        self.bump_message_count();
         Ok(ciphertext)
     }
 
@@ -275,25 +501,51 @@ impl DryocStream<Push> {
     }
 }
 
-impl DryocStream<Pull> {
+impl<C: SecretStreamCipher> DryocStream<Pull, C> {
     /// Returns a new pull stream, initialized from `key` and `header`.
-    pub fn init_pull<
-        Key: ByteArray<CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_KEYBYTES>,
-        Header: ByteArray<CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_HEADERBYTES>,
-    >(
-        key: &Key,
-        header: &Header,
-    ) -> Self {
-        let mut state = State::new();
-        crypto_secretstream_xchacha20poly1305_init_pull(
-            &mut state,
-            header.as_array(),
-            key.as_array(),
-        );
-        Self {
+    ///
+    /// Returns an [`Error`] if `key`'s length doesn't match
+    /// `C::KEYBYTES`, or `header`'s length doesn't match
+    /// `C::HEADERBYTES` (see [`DryocStream::init_push`] for why these are
+    /// runtime-checked rather than fixed-size array bounds).
+    pub fn init_pull<Key: Bytes, Header: Bytes>(key: &Key, header: &Header) -> Result<Self, Error> {
+        if key.as_slice().len() != C::KEYBYTES {
+            return Err(Error::Unknown(format!(
+                "key must be {} bytes for this cipher, got {}",
+                C::KEYBYTES,
+                key.as_slice().len()
+            )));
+        }
+        if header.as_slice().len() != C::HEADERBYTES {
+            return Err(Error::Unknown(format!(
+                "header must be {} bytes for this cipher, got {}",
+                C::HEADERBYTES,
+                header.as_slice().len()
+            )));
+        }
+        let state = C::init_pull(key.as_slice(), header.as_slice());
+        Ok(Self {
             state,
             phantom: std::marker::PhantomData,
-        }
+            rekey_interval: None,
+            message_count: 0,
+        })
+    }
+
+    /// Returns a new pull stream, initialized from `key` and `header`,
+    /// which automatically calls [`DryocStream::rekey`] every
+    /// `rekey_interval` calls to [`DryocStream::pull`]. This must match
+    /// the `rekey_interval` given to the push side's
+    /// [`DryocStream::with_rekey_interval`] so both sides rekey at the
+    /// same message boundary.
+    pub fn with_rekey_interval<Key: Bytes, Header: Bytes>(
+        key: &Key,
+        header: &Header,
+        rekey_interval: u64,
+    ) -> Result<Self, Error> {
+        let mut stream = Self::init_pull(key, header)?;
+        stream.set_rekey_interval(rekey_interval)?;
+        Ok(stream)
     }
 
     /// Decrypts `ciphertext` for this stream with `associated_data`, returning
@@ -303,20 +555,15 @@ impl DryocStream<Pull> {
         ciphertext: &Input,
         associated_data: Option<&Input>,
     ) -> Result<(Output, Tag), Error> {
-        use crate::constants::CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_ABYTES;
         let mut message = Output::default();
-        message.resize(
-            ciphertext.as_slice().len() - CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_ABYTES,
-            0,
-        );
-        let mut tag = 0u8;
-        crypto_secretstream_xchacha20poly1305_pull(
+        message.resize(ciphertext.as_slice().len() - C::ABYTES, 0);
+        let tag = C::pull(
             &mut self.state,
             message.as_mut_slice(),
-            &mut tag,
             ciphertext.as_slice(),
             associated_data.map(|aad| aad.as_slice()),
         )?;
+        self.bump_message_count();
 
         Ok((message, Tag::from_bits(tag).expect("invalid tag")))
     }
@@ -332,6 +579,587 @@ impl DryocStream<Pull> {
     }
 }
 
+/// Number of bytes used to encode each of the `opslimit`, `memlimit`, and
+/// `alg` pwhash parameters embedded in a
+/// [`DryocStream::seal_with_passphrase`] blob.
+const PWHASH_PARAM_BYTES: usize = 8;
+
+fn derive_passphrase_key<Passphrase: Bytes>(
+    passphrase: &Passphrase,
+    salt: &PwHashSalt,
+    opslimit: u64,
+    memlimit: u64,
+    alg: u64,
+) -> Result<Key, Error> {
+    let mut key = Key::new_byte_array();
+    crypto_pwhash(
+        key.as_mut_slice(),
+        passphrase.as_slice(),
+        salt.as_slice(),
+        opslimit,
+        memlimit as usize,
+        alg as _,
+    )?;
+    Ok(key)
+}
+
+impl DryocStream<Push> {
+    /// Derives a [`Key`] from `passphrase` using `crypto_pwhash`, encrypts
+    /// `message` with it, and returns a single self-describing blob laid
+    /// out as `salt || opslimit || memlimit || alg || cipher-kind ||
+    /// header || ciphertext`, with the pwhash parameters, [`CipherKind`],
+    /// and [`Header`] embedded so [`DryocStream::open_with_passphrase`]
+    /// needs nothing but the blob and the original passphrase to recover
+    /// `message`.
+    ///
+    /// Uses [`CRYPTO_PWHASH_OPSLIMIT_INTERACTIVE`] and
+    /// [`CRYPTO_PWHASH_MEMLIMIT_INTERACTIVE`] as the pwhash limits.
+    pub fn seal_with_passphrase<Passphrase: Bytes, Message: Bytes>(
+        passphrase: &Passphrase,
+        message: &Message,
+        associated_data: Option<&Message>,
+    ) -> Result<Vec<u8>, Error> {
+        let salt = PwHashSalt::gen();
+        let opslimit = CRYPTO_PWHASH_OPSLIMIT_INTERACTIVE as u64;
+        let memlimit = CRYPTO_PWHASH_MEMLIMIT_INTERACTIVE as u64;
+        let alg = CRYPTO_PWHASH_ALG_DEFAULT as u64;
+        let key = derive_passphrase_key(passphrase, &salt, opslimit, memlimit, alg)?;
+
+        let (mut stream, header): (_, Header) = Self::init_push(&key)?;
+        let ciphertext: Vec<u8> = stream.push(message, associated_data, Tag::FINAL)?;
+
+        let mut blob = Vec::with_capacity(
+            salt.as_slice().len()
+                + PWHASH_PARAM_BYTES * 3
+                + 1
+                + header.as_slice().len()
+                + ciphertext.len(),
+        );
+        blob.extend_from_slice(salt.as_slice());
+        blob.extend_from_slice(&opslimit.to_be_bytes());
+        blob.extend_from_slice(&memlimit.to_be_bytes());
+        blob.extend_from_slice(&alg.to_be_bytes());
+        blob.push(DryocStream::<Push>::kind() as u8);
+        blob.extend_from_slice(header.as_slice());
+        blob.extend_from_slice(&ciphertext);
+        Ok(blob)
+    }
+}
+
+impl DryocStream<Pull> {
+    /// Parses a blob produced by [`DryocStream::seal_with_passphrase`],
+    /// re-derives the [`Key`] from `passphrase` using the embedded pwhash
+    /// parameters, and decrypts the message it contains. Returns an
+    /// [`Error`] if the blob's [`CipherKind`] byte doesn't match
+    /// [`DryocStream::kind`] for this stream's backend.
+    pub fn open_with_passphrase<Passphrase: Bytes>(
+        passphrase: &Passphrase,
+        blob: &[u8],
+        associated_data: Option<&Vec<u8>>,
+    ) -> Result<Vec<u8>, Error> {
+        let salt_len = CRYPTO_PWHASH_SALTBYTES;
+        let header_len = CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_HEADERBYTES;
+        let prefix_len = salt_len + PWHASH_PARAM_BYTES * 3 + 1;
+
+        // The ciphertext following the header must be at least big enough to
+        // hold the AEAD tag, or `pull()` would underflow computing the
+        // plaintext length.
+        if blob.len() < prefix_len + header_len + CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_ABYTES {
+            return Err(Error::Unknown(
+                "passphrase-sealed blob is too short to be valid".to_string(),
+            ));
+        }
+
+        let salt = PwHashSalt::try_from(&blob[..salt_len]).expect("salt length");
+        let opslimit = u64::from_be_bytes(
+            blob[salt_len..salt_len + PWHASH_PARAM_BYTES]
+                .try_into()
+                .expect("opslimit slice"),
+        );
+        let memlimit = u64::from_be_bytes(
+            blob[salt_len + PWHASH_PARAM_BYTES..salt_len + PWHASH_PARAM_BYTES * 2]
+                .try_into()
+                .expect("memlimit slice"),
+        );
+        let alg = u64::from_be_bytes(
+            blob[salt_len + PWHASH_PARAM_BYTES * 2..salt_len + PWHASH_PARAM_BYTES * 3]
+                .try_into()
+                .expect("alg slice"),
+        );
+        let kind_byte = blob[prefix_len - 1];
+        let kind = CipherKind::try_from(kind_byte)?;
+        if kind != DryocStream::<Pull>::kind() {
+            return Err(Error::Unknown(format!(
+                "blob was sealed with cipher {:?}, but this stream expects {:?}",
+                kind,
+                DryocStream::<Pull>::kind()
+            )));
+        }
+
+        let key = derive_passphrase_key(passphrase, &salt, opslimit, memlimit, alg)?;
+
+        let header =
+            Header::try_from(&blob[prefix_len..prefix_len + header_len]).expect("header length");
+        let ciphertext = &blob[prefix_len + header_len..];
+
+        let mut stream = Self::init_pull(&key, &header)?;
+        let (message, _tag): (Vec<u8>, Tag) = stream.pull(&ciphertext.to_vec(), associated_data)?;
+        Ok(message)
+    }
+}
+
+/// Size, in bytes, of each plaintext block processed by
+/// [`DryocStreamWriter`] and [`DryocStreamReader`].
+pub const BLOCK_SIZE: usize = 64 * 1024;
+
+/// Size, in bytes, of the big-endian frame-length prefix written before
+/// each ciphertext block.
+const FRAME_LEN_BYTES: usize = 4;
+
+fn stream_error_to_io(error: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
+
+fn truncated_stream_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "stream ended before a Tag::FINAL block was received",
+    )
+}
+
+/// Maps a `read_exact` failure to [`truncated_stream_error`] when it's a
+/// genuine premature end of stream, leaving other IO errors (a broken
+/// pipe, a permission error, etc.) untouched so they aren't misreported
+/// as truncation.
+fn read_error_to_io(error: io::Error) -> io::Error {
+    if error.kind() == io::ErrorKind::UnexpectedEof {
+        truncated_stream_error()
+    } else {
+        error
+    }
+}
+
+/// Maximum size, in bytes, of a single ciphertext frame: a full
+/// [`BLOCK_SIZE`] plaintext block plus the AEAD tag overhead.
+const MAX_FRAME_LEN: usize = BLOCK_SIZE + CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_ABYTES;
+
+/// Validates a frame length read from the wire, before it's used to size
+/// an allocation: it must be large enough to hold at least the AEAD tag
+/// (so `pull()`'s ciphertext-length-minus-tag subtraction can't
+/// underflow), and no larger than a full [`BLOCK_SIZE`] block's worth of
+/// ciphertext (so a corrupt or malicious peer can't force an unbounded
+/// allocation by claiming an enormous frame length). Shared by the
+/// blocking [`DryocStreamReader`] and the `tokio` decrypt helpers.
+fn validate_frame_len(len: usize) -> io::Result<usize> {
+    if len < CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_ABYTES || len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "invalid frame length {} (must be between {} and {} bytes)",
+                len, CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_ABYTES, MAX_FRAME_LEN
+            ),
+        ));
+    }
+    Ok(len)
+}
+
+/// Wraps a [`std::io::Write`] destination with a [`DryocStream`] push
+/// stream, splitting the data written to it into fixed-size
+/// ([`BLOCK_SIZE`]) blocks.
+///
+/// The stream [`Header`] is written to the underlying writer as soon as
+/// the [`DryocStreamWriter`] is created. Each call to
+/// [`Write::write`](std::io::Write::write) buffers plaintext until a full
+/// block is available, at which point it's encrypted with
+/// [`Tag::MESSAGE`] and written as a 4-byte big-endian length prefix
+/// followed by the ciphertext. Call [`DryocStreamWriter::finish`] once all
+/// the data has been written, which flushes any remaining buffered
+/// plaintext (even if empty) as a final block tagged with [`Tag::FINAL`],
+/// allowing the reading side to detect a truncated stream.
+pub struct DryocStreamWriter<W: Write> {
+    stream: DryocStream<Push>,
+    writer: W,
+    buf: Vec<u8>,
+    associated_data: Option<Vec<u8>>,
+}
+
+impl<W: Write> DryocStreamWriter<W> {
+    /// Creates a new [`DryocStreamWriter`] using `key`, writing the stream
+    /// [`Header`] to `writer` immediately. `associated_data`, when
+    /// provided, is authenticated (but not encrypted) alongside every
+    /// block.
+    pub fn new(key: &Key, writer: W, associated_data: Option<Vec<u8>>) -> io::Result<Self> {
+        let pair = DryocStream::init_push(key).map_err(stream_error_to_io)?;
+        Self::from_stream_and_header(pair, writer, associated_data)
+    }
+
+    /// Creates a new [`DryocStreamWriter`] like [`DryocStreamWriter::new`],
+    /// but automatically calls [`DryocStream::rekey`] every
+    /// `rekey_interval` blocks (see [`DEFAULT_REKEY_INTERVAL`] for a
+    /// sensible default), bounding the amount of data protected by any
+    /// single key over a long-running stream. The reading side must be
+    /// created with [`DryocStreamReader::with_rekey_interval`] using the
+    /// same `rekey_interval` so both ends rekey at the same block
+    /// boundary.
+    pub fn with_rekey_interval(
+        key: &Key,
+        writer: W,
+        associated_data: Option<Vec<u8>>,
+        rekey_interval: u64,
+    ) -> io::Result<Self> {
+        let pair = DryocStream::<Push>::with_rekey_interval(key, rekey_interval)
+            .map_err(stream_error_to_io)?;
+        Self::from_stream_and_header(pair, writer, associated_data)
+    }
+
+    fn from_stream_and_header(
+        (stream, header): (DryocStream<Push>, Header),
+        mut writer: W,
+        associated_data: Option<Vec<u8>>,
+    ) -> io::Result<Self> {
+        writer.write_all(header.as_slice())?;
+        Ok(Self {
+            stream,
+            writer,
+            buf: Vec::with_capacity(BLOCK_SIZE),
+            associated_data,
+        })
+    }
+
+    fn write_frame(&mut self, tag: Tag) -> io::Result<()> {
+        let ciphertext: Vec<u8> = self
+            .stream
+            .push(&self.buf, self.associated_data.as_ref(), tag)
+            .map_err(stream_error_to_io)?;
+        self.buf.clear();
+        self.writer
+            .write_all(&(ciphertext.len() as u32).to_be_bytes())?;
+        self.writer.write_all(&ciphertext)
+    }
+
+    /// Flushes any remaining buffered plaintext as a final,
+    /// [`Tag::FINAL`]-tagged block, and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_frame(Tag::FINAL)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> Write for DryocStreamWriter<W> {
+    fn write(&mut self, mut buf: &[u8]) -> io::Result<usize> {
+        let total = buf.len();
+        while !buf.is_empty() {
+            let space = BLOCK_SIZE - self.buf.len();
+            let take = space.min(buf.len());
+            self.buf.extend_from_slice(&buf[..take]);
+            buf = &buf[take..];
+            if self.buf.len() == BLOCK_SIZE {
+                self.write_frame(Tag::MESSAGE)?;
+            }
+        }
+        Ok(total)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Wraps a [`std::io::Read`] source with a [`DryocStream`] pull stream,
+/// reconstructing the fixed-size blocks written by a
+/// [`DryocStreamWriter`].
+///
+/// The stream [`Header`] is read from `reader` as soon as the
+/// [`DryocStreamReader`] is created. Each block is read as a 4-byte
+/// big-endian length prefix followed by that many bytes of ciphertext,
+/// which is decrypted and authenticated with `associated_data` (which
+/// must match the value used when writing). Reading stops once a block
+/// tagged [`Tag::FINAL`] is seen; hitting the end of `reader` before a
+/// [`Tag::FINAL`] block arrives is treated as a truncated stream and
+/// returns an [`std::io::ErrorKind::UnexpectedEof`] error.
+pub struct DryocStreamReader<R: Read> {
+    stream: DryocStream<Pull>,
+    reader: R,
+    buf: Vec<u8>,
+    pos: usize,
+    associated_data: Option<Vec<u8>>,
+    finished: bool,
+}
+
+impl<R: Read> DryocStreamReader<R> {
+    /// Creates a new [`DryocStreamReader`] using `key`, reading the stream
+    /// [`Header`] from `reader` immediately.
+    pub fn new(key: &Key, reader: R, associated_data: Option<Vec<u8>>) -> io::Result<Self> {
+        Self::new_impl(key, reader, associated_data, None)
+    }
+
+    /// Creates a new [`DryocStreamReader`] like [`DryocStreamReader::new`],
+    /// but automatically calls [`DryocStream::rekey`] every
+    /// `rekey_interval` blocks. This must match the `rekey_interval` given
+    /// to the writing side's [`DryocStreamWriter::with_rekey_interval`] so
+    /// both ends rekey at the same block boundary.
+    pub fn with_rekey_interval(
+        key: &Key,
+        reader: R,
+        associated_data: Option<Vec<u8>>,
+        rekey_interval: u64,
+    ) -> io::Result<Self> {
+        Self::new_impl(key, reader, associated_data, Some(rekey_interval))
+    }
+
+    fn new_impl(
+        key: &Key,
+        mut reader: R,
+        associated_data: Option<Vec<u8>>,
+        rekey_interval: Option<u64>,
+    ) -> io::Result<Self> {
+        let mut header = Header::new_byte_array();
+        reader
+            .read_exact(header.as_mut_slice())
+            .map_err(read_error_to_io)?;
+        let stream = match rekey_interval {
+            Some(rekey_interval) => {
+                DryocStream::<Pull>::with_rekey_interval(key, &header, rekey_interval)
+                    .map_err(stream_error_to_io)?
+            }
+            None => DryocStream::init_pull(key, &header).map_err(stream_error_to_io)?,
+        };
+        Ok(Self {
+            stream,
+            reader,
+            buf: Vec::new(),
+            pos: 0,
+            associated_data,
+            finished: false,
+        })
+    }
+
+    fn fill_block(&mut self) -> io::Result<()> {
+        let mut len_buf = [0u8; FRAME_LEN_BYTES];
+        self.reader
+            .read_exact(&mut len_buf)
+            .map_err(read_error_to_io)?;
+        let len = validate_frame_len(u32::from_be_bytes(len_buf) as usize)?;
+
+        let mut ciphertext = vec![0u8; len];
+        self.reader
+            .read_exact(&mut ciphertext)
+            .map_err(read_error_to_io)?;
+
+        let (message, tag): (Vec<u8>, Tag) = self
+            .stream
+            .pull(&ciphertext, self.associated_data.as_ref())
+            .map_err(stream_error_to_io)?;
+
+        self.buf = message;
+        self.pos = 0;
+        if tag.contains(Tag::FINAL) {
+            self.finished = true;
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DryocStreamReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos >= self.buf.len() {
+            if self.finished {
+                return Ok(0);
+            }
+            self.fill_block()?;
+        }
+
+        let available = self.buf.len() - self.pos;
+        let n = available.min(buf.len());
+        buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(all(feature = "tokio", doc), doc(cfg(feature = "tokio")))]
+pub mod nonblocking {
+    //! # Async streaming support for [`DryocStream`]
+    //!
+    //! This mod provides `async` equivalents of [`DryocStreamWriter`] and
+    //! [`DryocStreamReader`], for use with [`tokio::io::AsyncRead`] and
+    //! [`tokio::io::AsyncWrite`]. They use the same on-wire framing: the
+    //! [`Header`] first, followed by a sequence of 4-byte big-endian
+    //! length-prefixed ciphertext blocks, with the last block tagged
+    //! [`Tag::FINAL`].
+    //!
+    //! Unlike the blocking adapters, these are implemented as a pair of
+    //! one-shot functions which drive a reader to completion against a
+    //! writer, yielding at each block so long-running transfers don't
+    //! block the executor.
+    use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+    use super::*;
+
+    impl DryocStream<Push> {
+        /// Encrypts all of `reader` into `writer`: initializes a fresh push
+        /// stream from `key`, writes its [`Header`] to `writer` first, then
+        /// a sequence of [`BLOCK_SIZE`]-sized [`Tag::MESSAGE`] blocks, and
+        /// finally a [`Tag::FINAL`] block once `reader` reaches EOF.
+        ///
+        /// `rekey_interval`, when given, is passed to
+        /// [`DryocStream::with_rekey_interval`] so the stream automatically
+        /// rekeys every `rekey_interval` blocks; the pull side must be
+        /// given the same interval to
+        /// [`decrypt_streams`](DryocStream::decrypt_streams) so both ends
+        /// rekey at the same block boundary.
+        pub async fn encrypt_streams<R, W>(
+            key: &Key,
+            rekey_interval: Option<u64>,
+            mut reader: R,
+            mut writer: W,
+            associated_data: Option<Vec<u8>>,
+        ) -> io::Result<()>
+        where
+            R: AsyncRead + Unpin,
+            W: AsyncWrite + Unpin,
+        {
+            let (mut stream, header): (_, Header) = match rekey_interval {
+                Some(rekey_interval) => Self::with_rekey_interval(key, rekey_interval)
+                    .map_err(stream_error_to_io)?,
+                None => Self::init_push(key).map_err(stream_error_to_io)?,
+            };
+            writer.write_all(header.as_slice()).await?;
+
+            let mut block = vec![0u8; BLOCK_SIZE];
+            loop {
+                let mut filled = 0;
+                while filled < block.len() {
+                    let n = reader.read(&mut block[filled..]).await?;
+                    if n == 0 {
+                        break;
+                    }
+                    filled += n;
+                }
+                let tag = if filled < block.len() {
+                    Tag::FINAL
+                } else {
+                    Tag::MESSAGE
+                };
+                block.truncate(filled);
+                let ciphertext: Vec<u8> = stream
+                    .push(&block, associated_data.as_ref(), tag)
+                    .map_err(stream_error_to_io)?;
+                writer
+                    .write_all(&(ciphertext.len() as u32).to_be_bytes())
+                    .await?;
+                writer.write_all(&ciphertext).await?;
+                if tag.contains(Tag::FINAL) {
+                    break;
+                }
+                block.resize(BLOCK_SIZE, 0);
+            }
+            writer.flush().await?;
+            Ok(())
+        }
+    }
+
+    impl DryocStream<Pull> {
+        /// Decrypts a stream produced by [`DryocStream::encrypt_streams`],
+        /// reading the [`Header`] from `reader` before decrypting each
+        /// block and writing the recovered plaintext to `writer`. Returns
+        /// once a [`Tag::FINAL`] block has been processed, or an
+        /// [`std::io::ErrorKind::UnexpectedEof`] error if `reader` runs out
+        /// before one arrives.
+        ///
+        /// `rekey_interval` must match the value (if any) given to the
+        /// push side's [`DryocStream::encrypt_streams`] call, so both ends
+        /// rekey at the same block boundary.
+        pub async fn decrypt_streams<R, W>(
+            key: &Key,
+            rekey_interval: Option<u64>,
+            mut reader: R,
+            mut writer: W,
+            associated_data: Option<Vec<u8>>,
+        ) -> io::Result<()>
+        where
+            R: AsyncRead + Unpin,
+            W: AsyncWrite + Unpin,
+        {
+            let mut header = Header::new_byte_array();
+            reader
+                .read_exact(header.as_mut_slice())
+                .await
+                .map_err(read_error_to_io)?;
+            let mut stream = match rekey_interval {
+                Some(rekey_interval) => {
+                    Self::with_rekey_interval(key, &header, rekey_interval)
+                        .map_err(stream_error_to_io)?
+                }
+                None => Self::init_pull(key, &header).map_err(stream_error_to_io)?,
+            };
+
+            loop {
+                let mut len_buf = [0u8; FRAME_LEN_BYTES];
+                reader
+                    .read_exact(&mut len_buf)
+                    .await
+                    .map_err(read_error_to_io)?;
+                let len = validate_frame_len(u32::from_be_bytes(len_buf) as usize)?;
+
+                let mut ciphertext = vec![0u8; len];
+                reader
+                    .read_exact(&mut ciphertext)
+                    .await
+                    .map_err(read_error_to_io)?;
+
+                let (message, tag): (Vec<u8>, Tag) = stream
+                    .pull(&ciphertext, associated_data.as_ref())
+                    .map_err(stream_error_to_io)?;
+                writer.write_all(&message).await?;
+                if tag.contains(Tag::FINAL) {
+                    break;
+                }
+            }
+            writer.flush().await?;
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_async_stream_roundtrip() {
+            let key = Key::gen();
+            let aad = b"stream metadata".to_vec();
+            let message = vec![0x42u8; BLOCK_SIZE * 2 + 137];
+
+            let mut encrypted = Vec::new();
+            DryocStream::encrypt_streams(
+                &key,
+                Some(2),
+                message.as_slice(),
+                &mut encrypted,
+                Some(aad.clone()),
+            )
+            .await
+            .expect("encrypt failed");
+
+            let mut decrypted = Vec::new();
+            DryocStream::decrypt_streams(
+                &key,
+                Some(2),
+                encrypted.as_slice(),
+                &mut decrypted,
+                Some(aad),
+            )
+            .await
+            .expect("decrypt failed");
+
+            assert_eq!(message, decrypted);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -350,7 +1178,8 @@ mod tests {
         let key = Key::gen();
 
         // Initialize the push side, type annotations required on return type
-        let (mut push_stream, header): (_, Header) = DryocStream::init_push(&key);
+        let (mut push_stream, header): (_, Header) =
+            DryocStream::<Push>::init_push(&key).expect("push init failed");
         // Encrypt a series of messages
         let c1: Vec<u8> = push_stream
             .push(message1, None, Tag::MESSAGE)
@@ -411,8 +1240,11 @@ mod tests {
             .expect("Encrypt failed");
 
         // Initialize the pull side using header generated by the push side
-        let mut pull_stream =
-            DryocStream::init_pull(&key, &Header::try_from(so_header.as_ref()).expect("header"));
+        let mut pull_stream = DryocStream::<Pull>::init_pull(
+            &key,
+            &Header::try_from(so_header.as_ref()).expect("header"),
+        )
+        .expect("pull init failed");
 
         // Decrypt the encrypted messages, type annotations required
         let (m1, tag1): (Vec<u8>, Tag) = pull_stream.pull(&c1, None).expect("Decrypt failed");
@@ -428,6 +1260,157 @@ mod tests {
         assert_eq!(tag3, Tag::FINAL);
     }
 
+    #[test]
+    fn test_stream_io_roundtrip() {
+        use std::io::Cursor;
+
+        let key = Key::gen();
+        let aad = b"stream metadata".to_vec();
+        let message = vec![0x42u8; BLOCK_SIZE * 2 + 137];
+
+        let mut sink = Cursor::new(Vec::new());
+        let mut writer =
+            DryocStreamWriter::new(&key, &mut sink, Some(aad.clone())).expect("writer init");
+        writer.write_all(&message).expect("write failed");
+        writer.finish().expect("finish failed");
+
+        let encrypted = sink.into_inner();
+        let mut reader =
+            DryocStreamReader::new(&key, Cursor::new(encrypted), Some(aad)).expect("reader init");
+        let mut decrypted = Vec::new();
+        reader.read_to_end(&mut decrypted).expect("read failed");
+
+        assert_eq!(message, decrypted);
+    }
+
+    #[test]
+    fn test_stream_io_rekey_interval() {
+        use std::io::Cursor;
+
+        let key = Key::gen();
+        let message = vec![0x42u8; BLOCK_SIZE * 3 + 17];
+
+        let mut sink = Cursor::new(Vec::new());
+        // Rekey every block, so the stream crosses several rekey
+        // boundaries over the course of the transfer.
+        let mut writer =
+            DryocStreamWriter::with_rekey_interval(&key, &mut sink, None, 1).expect("writer init");
+        writer.write_all(&message).expect("write failed");
+        writer.finish().expect("finish failed");
+
+        let encrypted = sink.into_inner();
+        let mut reader =
+            DryocStreamReader::with_rekey_interval(&key, Cursor::new(encrypted), None, 1)
+                .expect("reader init");
+        let mut decrypted = Vec::new();
+        reader.read_to_end(&mut decrypted).expect("read failed");
+
+        assert_eq!(message, decrypted);
+    }
+
+    #[test]
+    fn test_stream_io_truncated() {
+        use std::io::Cursor;
+
+        let key = Key::gen();
+        let mut sink = Cursor::new(Vec::new());
+        let mut writer = DryocStreamWriter::new(&key, &mut sink, None).expect("writer init");
+        // Write enough blocks that the stream has more than one frame, then
+        // call `finish` so a `Tag::FINAL` block is written too.
+        writer
+            .write_all(&vec![0u8; BLOCK_SIZE * 2 + 17])
+            .expect("write failed");
+        writer.finish().expect("finish failed");
+
+        let complete = sink.into_inner();
+        // Chop off the last byte of the last frame, so the reader hits EOF
+        // partway through reading a ciphertext frame instead of cleanly at
+        // a frame boundary.
+        let mut truncated = complete.clone();
+        truncated.truncate(truncated.len() - 1);
+        assert!(truncated.len() < complete.len());
+
+        let mut reader =
+            DryocStreamReader::new(&key, Cursor::new(truncated), None).expect("reader init");
+        let mut decrypted = Vec::new();
+        let err = reader.read_to_end(&mut decrypted).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_stream_rekey_interval() {
+        let key = Key::gen();
+        let messages: Vec<&[u8]> = vec![b"one", b"two", b"three", b"four", b"five"];
+
+        // Rekey every 2 messages, so both sides cross several rekey
+        // boundaries over the course of the stream.
+        let (mut push_stream, header): (_, Header) =
+            DryocStream::<Push>::with_rekey_interval(&key, 2).expect("push stream init failed");
+        let mut pull_stream = DryocStream::<Pull>::with_rekey_interval(&key, &header, 2)
+            .expect("pull stream init failed");
+
+        for (i, message) in messages.iter().enumerate() {
+            let tag = if i == messages.len() - 1 {
+                Tag::FINAL
+            } else {
+                Tag::MESSAGE
+            };
+            let ciphertext: Vec<u8> = push_stream
+                .push(message, None, tag)
+                .expect("encrypt failed");
+            let (decrypted, pulled_tag): (Vec<u8>, Tag) =
+                pull_stream.pull(&ciphertext, None).expect("decrypt failed");
+            assert_eq!(*message, decrypted.as_slice());
+            assert_eq!(tag, pulled_tag);
+        }
+    }
+
+    #[test]
+    fn test_stream_rekey_interval_rejects_zero() {
+        let key = Key::gen();
+
+        assert!(
+            DryocStream::<Push>::with_rekey_interval::<_, Header>(&key, 0).is_err(),
+            "a zero rekey_interval must be rejected"
+        );
+
+        let (_, header): (_, Header) =
+            DryocStream::<Push>::init_push(&key).expect("push init failed");
+        assert!(
+            DryocStream::<Pull>::with_rekey_interval(&key, &header, 0).is_err(),
+            "a zero rekey_interval must be rejected"
+        );
+    }
+
+    #[test]
+    fn test_stream_passphrase_seal_open() {
+        let passphrase = b"correct horse battery staple";
+        let message = b"Arbitrary data to encrypt".to_vec();
+        let aad = b"stream metadata".to_vec();
+
+        let blob = DryocStream::seal_with_passphrase(passphrase, &message, Some(&aad))
+            .expect("seal failed");
+
+        let decrypted = DryocStream::open_with_passphrase(passphrase, &blob, Some(&aad))
+            .expect("open failed");
+        assert_eq!(message, decrypted);
+
+        // Wrong passphrase must not decrypt the blob.
+        DryocStream::open_with_passphrase(b"wrong passphrase", &blob, Some(&aad))
+            .expect_err("open should have failed with the wrong passphrase");
+    }
+
+    #[test]
+    fn test_stream_cipher_kind() {
+        assert_eq!(DryocStream::<Push>::kind(), CipherKind::XChaCha20Poly1305);
+        assert_eq!(DryocStream::<Pull>::kind(), CipherKind::XChaCha20Poly1305);
+        assert_eq!(
+            CipherKind::try_from(1u8).expect("valid kind"),
+            CipherKind::XChaCha20Poly1305
+        );
+        assert!(CipherKind::try_from(0xffu8).is_err());
+    }
+
     #[cfg(feature = "nightly")]
     #[test]
     fn test_protected_memory() {
@@ -441,7 +1424,8 @@ mod tests {
         let key = protected::Key::gen_locked().expect("gen locked");
 
         // Initialize the push side, type annotations required on return type
-        let (mut push_stream, header): (_, Header) = DryocStream::init_push(&key);
+        let (mut push_stream, header): (_, Header) =
+            DryocStream::<Push>::init_push(&key).expect("push init failed");
 
         // Set secret key memory to no-access, but it must be unlocked first
         let key = key
@@ -465,7 +1449,8 @@ mod tests {
         let key = key.mprotect_readonly().expect("mprotect");
 
         // Initialize the pull side using header generated by the push side
-        let mut pull_stream = DryocStream::init_pull(&key, &header);
+        let mut pull_stream =
+            DryocStream::<Pull>::init_pull(&key, &header).expect("pull init failed");
 
         // Set secret key memory to no-access
         let _key = key.mprotect_noaccess().expect("mprotect");